@@ -44,18 +44,63 @@ pub enum ErrorKind {
     InvalidAlgorithmName,
     /// When a key is provided with an invalid format
     InvalidKeyFormat,
+    /// When a key of one kind (e.g. RSA) is handed to an algorithm that expects another
+    /// kind (e.g. ECDSA)
+    WrongKeyType {
+        /// The kind of key the algorithm expected, e.g. `"ECDSA"`
+        expected: ::alloc::string::String,
+        /// The kind of key that was actually given, e.g. `"RSA"`
+        actual: ::alloc::string::String,
+    },
+
+    // JWE errors
+    /// When the parameters given to `encrypt`/`decrypt` don't form a valid encryption
+    InvalidEncryption,
+    /// When the content encryption algorithm in a JWE header isn't one we support
+    UnsupportedContentEncryption,
+    /// When a JWE's ciphertext or authentication tag fails to decrypt/verify
+    DecryptionFailed,
+
+    // JWKS errors
+    /// When none of the JWKs in a `Jwks` match the `kid` from the token header
+    NoMatchingKey,
+    /// When the token header has no `kid` and the `Jwks` does not hold exactly one key
+    MissingKid,
+    /// When a JWK's `kty` isn't one we know how to build a verification key from
+    UnsupportedKeyType,
 
     // Validation errors
     /// When a token’s `exp` claim indicates that it has expired
-    ExpiredSignature,
+    ExpiredSignature {
+        /// How many seconds ago the token expired
+        expired_by_secs: i64,
+    },
     /// When a token’s `iss` claim does not match the expected issuer
-    InvalidIssuer,
+    InvalidIssuer {
+        /// The issuers the validation accepted
+        expected: ::alloc::vec::Vec<::alloc::string::String>,
+        /// The issuer found in the token, if any
+        found: Option<::alloc::string::String>,
+    },
     /// When a token’s `aud` claim does not match one of the expected audience values
-    InvalidAudience,
+    InvalidAudience {
+        /// The audiences the validation accepted
+        expected: ::alloc::vec::Vec<::alloc::string::String>,
+        /// The audience found in the token, if any
+        found: Option<::alloc::string::String>,
+    },
     /// When a token’s `aud` claim does not match one of the expected audience values
-    InvalidSubject,
+    InvalidSubject {
+        /// The subjects the validation accepted
+        expected: ::alloc::vec::Vec<::alloc::string::String>,
+        /// The subject found in the token, if any
+        found: Option<::alloc::string::String>,
+    },
     /// When a token’s nbf claim represents a time in the future
-    ImmatureSignature,
+    ImmatureSignature {
+        /// How many seconds from now the token will become valid
+        valid_in_secs: i64,
+    },
     /// When the algorithm in the header doesn't match the one passed to `decode`
     InvalidAlgorithm,
 
@@ -85,14 +130,21 @@ impl ErrorKind {
             ErrorKind::InvalidSignature => Some("invalid signature"),
             ErrorKind::InvalidEcdsaKey => Some("invalid ECDSA key"),
             ErrorKind::InvalidRsaKey => Some("invalid RSA key"),
-            ErrorKind::ExpiredSignature => Some("expired signature"),
-            ErrorKind::InvalidIssuer => Some("invalid issuer"),
-            ErrorKind::InvalidAudience => Some("invalid audience"),
-            ErrorKind::InvalidSubject => Some("invalid subject"),
-            ErrorKind::ImmatureSignature => Some("immature signature"),
+            ErrorKind::ExpiredSignature { .. } => Some("expired signature"),
+            ErrorKind::InvalidIssuer { .. } => Some("invalid issuer"),
+            ErrorKind::InvalidAudience { .. } => Some("invalid audience"),
+            ErrorKind::InvalidSubject { .. } => Some("invalid subject"),
+            ErrorKind::ImmatureSignature { .. } => Some("immature signature"),
             ErrorKind::InvalidAlgorithm => Some("algorithms don't match"),
             ErrorKind::InvalidAlgorithmName => Some("not a known algorithm"),
             ErrorKind::InvalidKeyFormat => Some("invalid key format"),
+            ErrorKind::WrongKeyType { .. } => Some("wrong key type"),
+            ErrorKind::InvalidEncryption => Some("invalid encryption parameters"),
+            ErrorKind::UnsupportedContentEncryption => Some("unsupported content encryption algorithm"),
+            ErrorKind::DecryptionFailed => Some("decryption failed"),
+            ErrorKind::NoMatchingKey => Some("no matching key found in the JWKS"),
+            ErrorKind::MissingKid => Some("token header is missing a kid"),
+            ErrorKind::UnsupportedKeyType => Some("unsupported JWK key type"),
             ErrorKind::__Nonexhaustive => Some("unknown error"),
             ErrorKind::Base64(_)
             | ErrorKind::Json(_)
@@ -111,14 +163,21 @@ impl std::error::Error for Error {
             | ErrorKind::InvalidSignature
             | ErrorKind::InvalidEcdsaKey
             | ErrorKind::InvalidRsaKey
-            | ErrorKind::ExpiredSignature
-            | ErrorKind::InvalidIssuer
-            | ErrorKind::InvalidAudience
-            | ErrorKind::InvalidSubject
-            | ErrorKind::ImmatureSignature
+            | ErrorKind::ExpiredSignature { .. }
+            | ErrorKind::InvalidIssuer { .. }
+            | ErrorKind::InvalidAudience { .. }
+            | ErrorKind::InvalidSubject { .. }
+            | ErrorKind::ImmatureSignature { .. }
             | ErrorKind::InvalidAlgorithm
             | ErrorKind::InvalidKeyFormat
             | ErrorKind::InvalidAlgorithmName
+            | ErrorKind::WrongKeyType { .. }
+            | ErrorKind::InvalidEncryption
+            | ErrorKind::UnsupportedContentEncryption
+            | ErrorKind::DecryptionFailed
+            | ErrorKind::NoMatchingKey
+            | ErrorKind::MissingKid
+            | ErrorKind::UnsupportedKeyType
             | ErrorKind::__Nonexhaustive => self.0.description().unwrap(),
             ErrorKind::Base64(ref err) => err.description(),
             ErrorKind::Json(ref err) => err.description(),
@@ -133,14 +192,21 @@ impl std::error::Error for Error {
             ErrorKind::InvalidSignature => None,
             ErrorKind::InvalidEcdsaKey => None,
             ErrorKind::InvalidRsaKey => None,
-            ErrorKind::ExpiredSignature => None,
-            ErrorKind::InvalidIssuer => None,
-            ErrorKind::InvalidAudience => None,
-            ErrorKind::InvalidSubject => None,
-            ErrorKind::ImmatureSignature => None,
+            ErrorKind::ExpiredSignature { .. } => None,
+            ErrorKind::InvalidIssuer { .. } => None,
+            ErrorKind::InvalidAudience { .. } => None,
+            ErrorKind::InvalidSubject { .. } => None,
+            ErrorKind::ImmatureSignature { .. } => None,
             ErrorKind::InvalidAlgorithm => None,
             ErrorKind::InvalidAlgorithmName => None,
             ErrorKind::InvalidKeyFormat => None,
+            ErrorKind::WrongKeyType { .. } => None,
+            ErrorKind::InvalidEncryption => None,
+            ErrorKind::UnsupportedContentEncryption => None,
+            ErrorKind::DecryptionFailed => None,
+            ErrorKind::NoMatchingKey => None,
+            ErrorKind::MissingKid => None,
+            ErrorKind::UnsupportedKeyType => None,
             ErrorKind::Base64(ref err) => Some(err),
             ErrorKind::Json(ref err) => Some(err),
             ErrorKind::Utf8(ref err) => Some(err),
@@ -157,15 +223,34 @@ impl fmt::Display for Error {
             | ErrorKind::InvalidSignature
             | ErrorKind::InvalidEcdsaKey
             | ErrorKind::InvalidRsaKey
-            | ErrorKind::ExpiredSignature
-            | ErrorKind::InvalidIssuer
-            | ErrorKind::InvalidAudience
-            | ErrorKind::InvalidSubject
-            | ErrorKind::ImmatureSignature
             | ErrorKind::InvalidAlgorithm
             | ErrorKind::InvalidKeyFormat
             | ErrorKind::InvalidAlgorithmName
+            | ErrorKind::InvalidEncryption
+            | ErrorKind::UnsupportedContentEncryption
+            | ErrorKind::DecryptionFailed
+            | ErrorKind::NoMatchingKey
+            | ErrorKind::MissingKid
+            | ErrorKind::UnsupportedKeyType
             | ErrorKind::__Nonexhaustive => write!(f, "{}", self.0.description().unwrap()),
+            ErrorKind::WrongKeyType { ref expected, ref actual } => {
+                write!(f, "wrong key type: expected a {} key, found a {} key", expected, actual)
+            }
+            ErrorKind::ExpiredSignature { expired_by_secs } => {
+                write!(f, "expired signature: expired {}s ago", expired_by_secs)
+            }
+            ErrorKind::ImmatureSignature { valid_in_secs } => {
+                write!(f, "immature signature: valid in {}s", valid_in_secs)
+            }
+            ErrorKind::InvalidIssuer { ref expected, ref found } => {
+                write!(f, "invalid issuer: expected one of {:?}, found {:?}", expected, found)
+            }
+            ErrorKind::InvalidAudience { ref expected, ref found } => {
+                write!(f, "invalid audience: expected one of {:?}, found {:?}", expected, found)
+            }
+            ErrorKind::InvalidSubject { ref expected, ref found } => {
+                write!(f, "invalid subject: expected one of {:?}, found {:?}", expected, found)
+            }
             ErrorKind::Json(ref err) => write!(f, "JSON error: {}", err),
             ErrorKind::Utf8(ref err) => write!(f, "UTF-8 error: {}", err),
             ErrorKind::Crypto(_) => write!(f, "Crypto error: undefined"),
@@ -198,14 +283,48 @@ impl From<::ring::error::Unspecified> for Error {
     }
 }
 
-impl From<::ring::error::KeyRejected> for Error {
-    fn from(_err: ::ring::error::KeyRejected) -> Error {
-        new_error(ErrorKind::InvalidEcdsaKey)
-    }
-}
-
 impl From<ErrorKind> for Error {
     fn from(kind: ErrorKind) -> Error {
         new_error(kind)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    #[test]
+    fn wrong_key_type_display_names_both_kinds() {
+        let err = Error::from(ErrorKind::WrongKeyType {
+            expected: "ECDSA".to_string(),
+            actual: "RSA".to_string(),
+        });
+        assert_eq!(err.to_string(), "wrong key type: expected a ECDSA key, found a RSA key");
+    }
+
+    #[test]
+    fn expired_signature_display_includes_delta() {
+        let err = Error::from(ErrorKind::ExpiredSignature { expired_by_secs: 42 });
+        assert_eq!(err.to_string(), "expired signature: expired 42s ago");
+    }
+
+    #[test]
+    fn immature_signature_display_includes_delta() {
+        let err = Error::from(ErrorKind::ImmatureSignature { valid_in_secs: 7 });
+        assert_eq!(err.to_string(), "immature signature: valid in 7s");
+    }
+
+    #[test]
+    fn invalid_issuer_display_includes_expected_and_found() {
+        let err = Error::from(ErrorKind::InvalidIssuer {
+            expected: vec!["https://issuer.example".to_string()],
+            found: Some("https://evil.example".to_string()),
+        });
+        assert_eq!(
+            err.to_string(),
+            r#"invalid issuer: expected one of ["https://issuer.example"], found Some("https://evil.example")"#
+        );
+    }
+}
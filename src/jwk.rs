@@ -0,0 +1,150 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use base64;
+use ring::signature;
+use serde_json;
+
+use crate::errors::{new_error, Error, ErrorKind, Result};
+
+/// A single JSON Web Key, as published in a JWKS document.
+///
+/// Only the fields needed to verify RSA-signed tokens are modeled here; unknown fields
+/// are ignored on deserialization.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Jwk {
+    /// The key type, e.g. `"RSA"`
+    pub kty: String,
+    /// The unique key ID used to match a token's header `kid`
+    pub kid: Option<String>,
+    /// The algorithm this key is intended to be used with, e.g. `"RS256"`
+    pub alg: Option<String>,
+    /// The RSA modulus, base64url-encoded
+    pub n: Option<String>,
+    /// The RSA public exponent, base64url-encoded
+    pub e: Option<String>,
+}
+
+impl Jwk {
+    /// Decode this JWK's modulus and exponent into the `ring` RSA public key components
+    /// used to verify an `RS256`/`RS384`/`RS512` signature.
+    ///
+    /// Returns `ErrorKind::UnsupportedKeyType` if this isn't an RSA key.
+    pub fn to_rsa_public_key_components(&self) -> Result<signature::RsaPublicKeyComponents<Vec<u8>>> {
+        if self.kty != "RSA" {
+            return Err(new_error(ErrorKind::UnsupportedKeyType));
+        }
+        let n = self.n.as_deref().ok_or_else(|| new_error(ErrorKind::UnsupportedKeyType))?;
+        let e = self.e.as_deref().ok_or_else(|| new_error(ErrorKind::UnsupportedKeyType))?;
+
+        Ok(signature::RsaPublicKeyComponents {
+            n: base64::decode_config(n, base64::URL_SAFE_NO_PAD)?,
+            e: base64::decode_config(e, base64::URL_SAFE_NO_PAD)?,
+        })
+    }
+}
+
+/// A set of JSON Web Keys, as published by an OIDC provider's `jwks_uri`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Jwks {
+    /// The keys in this set
+    pub keys: Vec<Jwk>,
+}
+
+impl Jwks {
+    /// Parse a `Jwks` from its standard JSON representation.
+    pub fn from_json(json: &str) -> Result<Jwks> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Select the key matching `kid`.
+    ///
+    /// If `kid` is `None`, this only succeeds when the set holds exactly one key;
+    /// otherwise it's ambiguous which key should be used and `ErrorKind::MissingKid`
+    /// is returned. If `kid` is `Some` but nothing matches, `ErrorKind::NoMatchingKey`
+    /// is returned.
+    pub fn find(&self, kid: Option<&str>) -> Result<&Jwk> {
+        match kid {
+            Some(kid) => {
+                self.keys.iter().find(|jwk| jwk.kid.as_deref() == Some(kid)).ok_or_else(|| new_error(ErrorKind::NoMatchingKey))
+            }
+            None => match self.keys.as_slice() {
+                [only] => Ok(only),
+                _ => Err(new_error(ErrorKind::MissingKid)),
+            },
+        }
+    }
+}
+
+/// Read a token header's `kid` and select the matching key out of a `Jwks`.
+///
+/// This performs key *selection* only; it does not verify the token's signature or claims.
+/// The returned `Jwk` gives the RSA public key through `to_rsa_public_key_components()`
+/// and the key's algorithm through `alg`.
+pub fn select_key_for_token<'a>(token: &str, jwks: &'a Jwks) -> Result<&'a Jwk> {
+    let header_segment = token.split('.').next().ok_or_else(|| new_error(ErrorKind::InvalidToken))?;
+    let header_json = base64::decode_config(header_segment, base64::URL_SAFE_NO_PAD)?;
+    let header: serde_json::Value = serde_json::from_slice(&header_json)?;
+    let kid = header.get("kid").and_then(|v| v.as_str());
+
+    jwks.find(kid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use alloc::vec;
+
+    fn jwk(kid: &str) -> Jwk {
+        Jwk {
+            kty: "RSA".into(),
+            kid: Some(kid.into()),
+            alg: Some("RS256".into()),
+            n: Some("n".into()),
+            e: Some("AQAB".into()),
+        }
+    }
+
+    fn token_with_header(header_json: &str) -> String {
+        let header = base64::encode_config(header_json, base64::URL_SAFE_NO_PAD);
+        format!("{}.e30.sig", header)
+    }
+
+    #[test]
+    fn from_json_parses_standard_jwks() {
+        let jwks = Jwks::from_json(
+            r#"{"keys":[{"kty":"RSA","kid":"key-1","alg":"RS256","n":"abc","e":"AQAB"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(jwks.keys.len(), 1);
+        assert_eq!(jwks.keys[0].kid.as_deref(), Some("key-1"));
+    }
+
+    #[test]
+    fn find_with_no_kid_and_single_key_succeeds() {
+        let jwks = Jwks { keys: vec![jwk("only")] };
+        let found = jwks.find(None).unwrap();
+        assert_eq!(found.kid.as_deref(), Some("only"));
+    }
+
+    #[test]
+    fn find_with_no_kid_and_multiple_keys_is_ambiguous() {
+        let jwks = Jwks { keys: vec![jwk("a"), jwk("b")] };
+        assert!(matches!(jwks.find(None).unwrap_err().kind(), ErrorKind::MissingKid));
+    }
+
+    #[test]
+    fn find_with_unmatched_kid_fails() {
+        let jwks = Jwks { keys: vec![jwk("a"), jwk("b")] };
+        assert!(matches!(jwks.find(Some("c")).unwrap_err().kind(), ErrorKind::NoMatchingKey));
+    }
+
+    #[test]
+    fn select_key_for_token_uses_header_kid() {
+        let jwks = Jwks { keys: vec![jwk("a"), jwk("b")] };
+        let token = token_with_header(r#"{"alg":"RS256","kid":"b"}"#);
+        let selected = select_key_for_token(&token, &jwks).unwrap();
+        assert_eq!(selected.kid.as_deref(), Some("b"));
+    }
+}